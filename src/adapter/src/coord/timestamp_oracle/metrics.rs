@@ -8,17 +8,73 @@
 // by the Apache License, Version 2.0.
 
 //! Prometheus monitoring metrics.
+//!
+//! [`ExternalOpMetrics::run_op`] and [`MetricsRetryStream::sleep`] also drive a parallel
+//! OpenTelemetry tracing layer off the same `timeline`/`op` labels: each attempt of an oracle
+//! call shows up as its own span, and each retry backoff as an event on whatever span is active
+//! in the caller's retry loop, alongside the usual counters.
 
+use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
+use anyhow::Context;
 use mz_ore::metric;
 use mz_ore::metrics::{Counter, IntCounter, MetricsRegistry};
 use mz_ore::stats::HISTOGRAM_COUNT_BUCKETS;
 use mz_postgres_client::metrics::PostgresClientMetrics;
+use opentelemetry::trace::TraceContextExt;
 use prometheus::{CounterVec, Histogram, HistogramVec, IntCounterVec};
+use tracing::Instrument;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
 
 use crate::coord::timestamp_oracle::retry::RetryStream;
 
+/// Bucket boundaries, in seconds, for [`MetricsVecs::external_op_latency_seconds`]. Spans from 1ms
+/// (a fast in-memory oracle round trip) up to ~17min (a badly wedged postgres-backed one).
+const HISTOGRAM_SECONDS_BUCKETS: &[f64] = &[
+    0.001, 0.002, 0.004, 0.008, 0.016, 0.032, 0.064, 0.128, 0.256, 0.512, 1.024, 2.048, 4.096,
+    8.192, 16.384, 32.768, 65.536, 131.072, 262.144, 524.288, 1048.576,
+];
+
+/// A coarse classification of why an oracle operation failed, recorded as the `error_kind` label
+/// on `mz_ts_oracle_failed_count` so operators can tell a flapping connection from a permanent
+/// problem without having to read logs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OracleErrorKind {
+    /// Likely to succeed if retried as-is (e.g. a dropped connection).
+    Retryable,
+    /// Failed due to contention with another writer (e.g. a serialization conflict).
+    Contention,
+    /// Not expected to succeed on retry (e.g. a schema mismatch).
+    Fatal,
+    /// Doesn't fit the other buckets, or the backing oracle doesn't classify its errors.
+    Other,
+}
+
+impl OracleErrorKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            OracleErrorKind::Retryable => "retryable",
+            OracleErrorKind::Contention => "contention",
+            OracleErrorKind::Fatal => "fatal",
+            OracleErrorKind::Other => "other",
+        }
+    }
+}
+
+/// Maps an operation failure to an [`OracleErrorKind`].
+///
+/// A single pluggable function rather than a trait: the in-memory oracle has no real failure
+/// modes to distinguish and can use [`default_error_classifier`], while the postgres-backed oracle
+/// supplies its own, mapping `tokio_postgres`/driver errors into these buckets.
+pub type ErrorClassifier = fn(&anyhow::Error) -> OracleErrorKind;
+
+/// The default [`ErrorClassifier`]: classifies nothing, since without a concrete backing store
+/// there's no driver-specific signal to key off of.
+pub fn default_error_classifier(_err: &anyhow::Error) -> OracleErrorKind {
+    OracleErrorKind::Other
+}
+
 /// Prometheus monitoring metrics for timestamp oracles.
 ///
 /// Intentionally not Clone because we expect this to be passed around in an
@@ -51,7 +107,10 @@ impl std::fmt::Debug for Metrics {
 impl Metrics {
     /// Returns a new [Metrics] instance for the given `timeline`, connected to
     /// the given registry.
-    pub fn new(registry: &MetricsRegistry, timeline: &str) -> Self {
+    ///
+    /// `classify_error` lets the caller plug in how operation failures map to an
+    /// [`OracleErrorKind`]; pass [`default_error_classifier`] absent a more specific one.
+    pub fn new(registry: &MetricsRegistry, timeline: &str, classify_error: ErrorClassifier) -> Self {
         let vecs = MetricsVecs::new(registry);
 
         // It's a bit annoying that we're encoding the timeline in the metric's
@@ -61,7 +120,7 @@ impl Metrics {
         let pg_client_metrics_prefix = format!("mz_ts_oracle_{}", timeline);
 
         Metrics {
-            oracle: vecs.oracle_metrics(timeline),
+            oracle: vecs.oracle_metrics(timeline, classify_error),
             batching: vecs.batching_metrics(timeline),
             retries: vecs.retries_metrics(timeline),
             postgres_client: PostgresClientMetrics::new(registry, &pg_client_metrics_prefix),
@@ -76,6 +135,7 @@ struct MetricsVecs {
     external_op_succeeded: IntCounterVec,
     external_op_failed: IntCounterVec,
     external_op_seconds: CounterVec,
+    external_op_latency_seconds: HistogramVec,
 
     retry_started: IntCounterVec,
     retry_finished: IntCounterVec,
@@ -101,13 +161,19 @@ impl MetricsVecs {
             external_op_failed: registry.register(metric!(
                 name: "mz_ts_oracle_failed_count",
                 help: "count of oracle operations failed",
-                var_labels: ["timeline", "op"],
+                var_labels: ["timeline", "op", "error_kind"],
             )),
             external_op_seconds: registry.register(metric!(
                 name: "mz_ts_oracle_seconds",
                 help: "time spent in oracle operations",
                 var_labels: ["timeline", "op"],
             )),
+            external_op_latency_seconds: registry.register(metric!(
+                name: "mz_ts_oracle_latency_seconds",
+                help: "distribution of time spent in oracle operations, exemplared by trace id",
+                var_labels: ["timeline", "op"],
+                buckets: HISTOGRAM_SECONDS_BUCKETS.to_vec(),
+            )),
 
             retry_started: registry.register(metric!(
                 name: "mz_ts_oracle_retry_started_count",
@@ -139,23 +205,34 @@ impl MetricsVecs {
         }
     }
 
-    fn oracle_metrics(&self, timeline: &str) -> OracleMetrics {
+    fn oracle_metrics(&self, timeline: &str, classify_error: ErrorClassifier) -> OracleMetrics {
         OracleMetrics {
-            write_ts: self.external_op_metrics("write_ts", timeline),
-            peek_write_ts: self.external_op_metrics("peek_write_ts", timeline),
-            read_ts: self.external_op_metrics("read_ts", timeline),
-            apply_write: self.external_op_metrics("apply_write", timeline),
+            write_ts: self.external_op_metrics("write_ts", timeline, classify_error),
+            peek_write_ts: self.external_op_metrics("peek_write_ts", timeline, classify_error),
+            read_ts: self.external_op_metrics("read_ts", timeline, classify_error),
+            apply_write: self.external_op_metrics("apply_write", timeline, classify_error),
         }
     }
 
-    fn external_op_metrics(&self, op: &str, timeline: &str) -> ExternalOpMetrics {
+    fn external_op_metrics(
+        &self,
+        op: &str,
+        timeline: &str,
+        classify_error: ErrorClassifier,
+    ) -> ExternalOpMetrics {
         ExternalOpMetrics {
+            op: op.to_owned(),
+            timeline: timeline.to_owned(),
+            classify_error,
             started: self.external_op_started.with_label_values(&[timeline, op]),
             succeeded: self
                 .external_op_succeeded
                 .with_label_values(&[timeline, op]),
-            failed: self.external_op_failed.with_label_values(&[timeline, op]),
+            failed: self.external_op_failed.clone(),
             seconds: self.external_op_seconds.with_label_values(&[timeline, op]),
+            latency_seconds: self
+                .external_op_latency_seconds
+                .with_label_values(&[timeline, op]),
         }
     }
 
@@ -193,30 +270,78 @@ impl MetricsVecs {
 
 #[derive(Debug)]
 pub struct ExternalOpMetrics {
+    op: String,
+    timeline: String,
+    classify_error: ErrorClassifier,
     started: IntCounter,
     succeeded: IntCounter,
-    failed: IntCounter,
+    failed: IntCounterVec,
     seconds: Counter,
+    latency_seconds: Histogram,
 }
 
 impl ExternalOpMetrics {
-    pub(crate) async fn run_op<R, F, OpFn>(&self, op_fn: OpFn) -> Result<R, anyhow::Error>
+    /// Runs `op_fn`, recording started/succeeded/failed counts, a latency observation, and an
+    /// OpenTelemetry span, around it. `attempt` is folded into the error context on failure (it's
+    /// the caller's retry loop that knows which attempt this is, e.g. via
+    /// [`MetricsRetryStream::attempt`]).
+    pub(crate) async fn run_op<R, F, OpFn>(
+        &self,
+        attempt: usize,
+        op_fn: OpFn,
+    ) -> Result<R, anyhow::Error>
     where
         F: std::future::Future<Output = Result<R, anyhow::Error>>,
         OpFn: FnOnce() -> F,
     {
         self.started.inc();
         let start = Instant::now();
-        let res = op_fn().await;
+
+        // A `tracing` span's own name must be a `'static` literal, so the per-op name (`write_ts`,
+        // `read_ts`, ...) is carried via the `otel.name` field instead, which `tracing-opentelemetry`
+        // recognizes as an override for the exported span's name.
+        let span = tracing::info_span!(
+            "oracle_op",
+            otel.name = self.op.as_str(),
+            timeline = self.timeline.as_str(),
+            op = self.op.as_str(),
+            outcome = tracing::field::Empty,
+        );
+        let res = op_fn().instrument(span.clone()).await;
+
         let elapsed_seconds = start.elapsed().as_secs_f64();
         self.seconds.inc_by(elapsed_seconds);
-        match res.as_ref() {
-            Ok(_) => self.succeeded.inc(),
-            Err(_err) => {
-                self.failed.inc();
+
+        // Attach the active trace as an exemplar when one is live, so a scrape of `/metrics` lets
+        // a dashboard jump from a tail-latency bucket straight to the trace of the slow call.
+        let trace_id = span.context().span().span_context().trace_id();
+        if trace_id != opentelemetry::trace::TraceId::INVALID {
+            let mut exemplar = HashMap::with_capacity(1);
+            exemplar.insert("trace_id".to_string(), trace_id.to_string());
+            self.latency_seconds
+                .observe_with_exemplar(elapsed_seconds, exemplar);
+        } else {
+            self.latency_seconds.observe(elapsed_seconds);
+        }
+
+        match res {
+            Ok(res) => {
+                self.succeeded.inc();
+                span.record("outcome", "ok");
+                Ok(res)
             }
-        };
-        res
+            Err(err) => {
+                let kind = (self.classify_error)(&err);
+                self.failed
+                    .with_label_values(&[&self.timeline, &self.op, kind.as_str()])
+                    .inc();
+                span.record("outcome", "err");
+                Err(err.context(format!(
+                    "timeline={} op={} attempt={}",
+                    self.timeline, self.op, attempt
+                )))
+            }
+        }
     }
 }
 
@@ -300,8 +425,15 @@ impl MetricsRetryStream {
     /// accidental mis-use.
     pub async fn sleep(self) -> Self {
         self.retries.inc();
-        self.sleep_seconds
-            .inc_by(self.retry.next_sleep().as_secs_f64());
+        let next_sleep = self.retry.next_sleep();
+        self.sleep_seconds.inc_by(next_sleep.as_secs_f64());
+        // Recorded as an event on whatever span is active at the call site. This is not the
+        // `ExternalOpMetrics::run_op` span: that one only covers a single attempt's `op_fn`.
+        tracing::info!(
+            attempt = self.retry.attempt(),
+            next_sleep = ?next_sleep,
+            "oracle operation backing off before retry"
+        );
         let retry = self.retry.sleep().await;
         MetricsRetryStream {
             retry,
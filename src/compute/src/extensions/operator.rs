@@ -7,17 +7,22 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use differential_dataflow::difference::{Abelian, Semigroup};
+use std::collections::BTreeMap;
+
+use differential_dataflow::difference::{Abelian, Multiply, Semigroup};
 use differential_dataflow::lattice::Lattice;
 use differential_dataflow::operators::arrange::{Arrange, Arranged, TraceAgent};
 use differential_dataflow::operators::reduce::ReduceCore;
+use differential_dataflow::trace::cursor::Cursor;
+use differential_dataflow::trace::implementations::ord::OrdValSpine;
 use differential_dataflow::trace::{Batch, Trace, TraceReader};
-use differential_dataflow::{Collection, Data, ExchangeData, Hashable};
+use differential_dataflow::{AsCollection, Collection, Data, ExchangeData, Hashable};
 use mz_repr::Row;
 use mz_storage_client::types::errors::DataflowError;
 use timely::container::columnation::Columnation;
-use timely::dataflow::channels::pact::{ParallelizationContract, Pipeline};
+use timely::dataflow::channels::pact::{Exchange, ParallelizationContract, Pipeline};
 use timely::dataflow::operators::generic::builder_rc::OperatorBuilder;
+use timely::dataflow::operators::{Broadcast, Capability};
 use timely::dataflow::Scope;
 use timely::progress::{Antichain, Timestamp};
 
@@ -130,6 +135,327 @@ where
     }
 }
 
+/// Extension trait for joining an [`Arranged`] collection against a second collection with no
+/// usable equi-key, by broadcasting the second collection instead of exchanging it by key.
+///
+/// Cross joins and inequality joins (`a.x < b.y`) have no join key to exchange data by, so the
+/// normal `mz_arrange`-then-exchange join strategy degenerates to shipping everything to a single
+/// worker. `mz_broadcast_join` instead arranges `self` as usual (with a trivial `()` key, since
+/// there's nothing to key it by) and broadcasts every update of `small` to every worker, so
+/// each worker ends up holding a full copy of `small` to match against its own local shard of
+/// `self`. This only pays off when `small` is, as the name suggests, small — broadcasting a large
+/// collection multiplies its size by the number of workers.
+pub(crate) trait MzBroadcastJoin<G, V, R>
+where
+    G: Scope,
+    G::Timestamp: Lattice,
+    V: Data,
+    R: Semigroup,
+{
+    /// Joins `self` against `small`, calling `logic` for every pair drawn from `self` and
+    /// `small`. `logic` returns `None` to discard a pair that doesn't satisfy the join predicate,
+    /// which is how inequality joins are expressed; a cross join's `logic` always returns `Some`.
+    fn mz_broadcast_join<V2, R2, D, L>(
+        &self,
+        small: &Collection<G, V2, R2>,
+        name: &str,
+        logic: L,
+    ) -> Collection<G, D, <R as Multiply<R2>>::Output>
+    where
+        V2: ExchangeData,
+        R2: ExchangeData,
+        R: Multiply<R2>,
+        <R as Multiply<R2>>::Output: Semigroup + 'static,
+        D: Data,
+        L: FnMut(&V, &V2) -> Option<D> + 'static;
+}
+
+impl<G, V, T1, R> MzBroadcastJoin<G, V, R> for Arranged<G, T1>
+where
+    G: Scope,
+    G::Timestamp: Lattice + Ord,
+    V: Data,
+    R: Semigroup,
+    T1: TraceReader<Key = (), Val = V, Time = G::Timestamp, R = R> + Clone + 'static,
+    T1::Batch: Batch,
+{
+    fn mz_broadcast_join<V2, R2, D, L>(
+        &self,
+        small: &Collection<G, V2, R2>,
+        name: &str,
+        mut logic: L,
+    ) -> Collection<G, D, <R as Multiply<R2>>::Output>
+    where
+        V2: ExchangeData,
+        R2: ExchangeData,
+        R: Multiply<R2>,
+        <R as Multiply<R2>>::Output: Semigroup + 'static,
+        D: Data,
+        L: FnMut(&V, &V2) -> Option<D> + 'static,
+    {
+        // Broadcast `small`'s updates (rather than the arranged side's) to every worker, since
+        // `small` is the side we've decided is cheap to replicate.
+        let broadcasted = small.inner.broadcast().as_collection();
+
+        let mut large_trace = self.trace.clone();
+        let mut builder = OperatorBuilder::new(format!("{name}BroadcastJoin"), self.stream.scope());
+        let mut large_input = builder.new_input(&self.stream, Pipeline);
+        let mut small_input = builder.new_input(&broadcasted.inner, Pipeline);
+        let (mut output, stream) = builder.new_output();
+
+        builder.build(move |_capability| {
+            // Every update `small` has ever seen, across all times. Unlike `large_trace`, `small`
+            // has no shared trace behind it to query later - it's a broadcast of a plain
+            // collection - so each worker must keep its own copy of the full history in order to
+            // match a freshly-arrived `large` batch against everything already known about
+            // `small`, not just whatever of `small` happens to arrive in the same activation.
+            let mut small_history: Vec<(V2, G::Timestamp, R2)> = Vec::new();
+
+            move |frontiers| {
+                let mut output = output.activate();
+
+                // New `large`-side batches, matched against `small`'s history up to (but not
+                // including) this round - `small`'s own new data is joined against the
+                // now-up-to-date `large_trace` below instead, so this doesn't double-count the
+                // pair of a `large` batch and a `small` batch that land in the same activation.
+                large_input.for_each(|time, data| {
+                    let mut session = output.session(&time);
+                    for batch in data.iter() {
+                        let mut cursor = batch.cursor();
+                        while cursor.key_valid(batch) {
+                            while cursor.val_valid(batch) {
+                                let large_val = cursor.val(batch);
+                                for (small_val, small_time, small_diff) in &small_history {
+                                    if let Some(datum) = logic(large_val, small_val) {
+                                        cursor.map_times(batch, |large_time, large_diff| {
+                                            let join_time = large_time.join(small_time);
+                                            let diff = large_diff.clone().multiply(small_diff);
+                                            session.give((datum.clone(), join_time, diff));
+                                        });
+                                    }
+                                }
+                                cursor.step_val(batch);
+                            }
+                            cursor.step_key(batch);
+                        }
+                    }
+                });
+
+                small_input.for_each(|time, data| {
+                    let mut session = output.session(&time);
+                    for (small_val, small_time, small_diff) in data.drain(..) {
+                        let (mut cursor, storage) = large_trace.cursor();
+                        while cursor.key_valid(&storage) {
+                            while cursor.val_valid(&storage) {
+                                let large_val = cursor.val(&storage);
+                                if let Some(datum) = logic(large_val, &small_val) {
+                                    cursor.map_times(&storage, |large_time, large_diff| {
+                                        let join_time = large_time.join(&small_time);
+                                        let diff = large_diff.clone().multiply(&small_diff);
+                                        session.give((datum.clone(), join_time, diff));
+                                    });
+                                }
+                                cursor.step_val(&storage);
+                            }
+                            cursor.step_key(&storage);
+                        }
+                        small_history.push((small_val, small_time, small_diff));
+                    }
+                });
+
+                // Only hold back compaction up to the frontier of the broadcast side; the large
+                // side's trace is kept alive by the arrangement that produced it.
+                let frontier = &frontiers[1].frontier();
+                large_trace.set_logical_compaction(frontier.borrow());
+                large_trace.set_physical_compaction(frontier.borrow());
+            }
+        });
+
+        stream.as_collection()
+    }
+}
+
+/// Extension trait implementing the "half join" building block used to compose worst-case-optimal
+/// delta joins without ever materializing an intermediate product.
+pub(crate) trait MzHalfJoin<G, K, V, R>
+where
+    G: Scope,
+    G::Timestamp: Lattice,
+    K: Data,
+    V: Data,
+    R: Semigroup,
+{
+    /// Joins `self` — tuples of `(key, value, time)`, where `time` is the logical time at which
+    /// the probe tuple should be considered to hold, which may differ from the outer dataflow time
+    /// at which it arrives — against `trace`.
+    ///
+    /// For each probe `(key, v, t)`, the cursor of `trace` is seeked to `key`, and for every
+    /// `(val, arr_time, diff)` for which `compare(&t, &arr_time)` holds, `output(&key, &v, &val)`
+    /// is emitted with difference `r.multiply(&diff)` at time `t.join(&arr_time)`. `compare`
+    /// encodes the delta join's total order over its participating relations, so that every
+    /// update to the full join is produced by exactly one half join; `delay` computes, for a given
+    /// probe time, the time at which `trace` must be complete before the probe can safely be
+    /// answered. Probes that aren't yet answerable are stashed against a retained capability and
+    /// re-attempted once `trace`'s frontier passes `delay`'s result.
+    fn mz_half_join<V2, T2, D, FF, CF, S>(
+        &self,
+        trace: &Arranged<G, T2>,
+        delay: FF,
+        compare: CF,
+        output: S,
+    ) -> Collection<G, D, <R as Multiply<T2::R>>::Output>
+    where
+        V2: Data,
+        T2: TraceReader<Key = K, Val = V2, Time = G::Timestamp> + Clone + 'static,
+        T2::R: Semigroup,
+        R: Multiply<T2::R>,
+        <R as Multiply<T2::R>>::Output: Semigroup + 'static,
+        D: Data,
+        FF: Fn(&G::Timestamp) -> G::Timestamp + 'static,
+        CF: Fn(&G::Timestamp, &G::Timestamp) -> bool + 'static,
+        S: Fn(&K, &V, &V2) -> D + 'static;
+}
+
+impl<G, K, V, R> MzHalfJoin<G, K, V, R> for Collection<G, (K, V, G::Timestamp), R>
+where
+    G: Scope,
+    G::Timestamp: Lattice + Ord,
+    K: ExchangeData + Hashable,
+    V: ExchangeData,
+    R: ExchangeData + Semigroup,
+{
+    fn mz_half_join<V2, T2, D, FF, CF, S>(
+        &self,
+        trace: &Arranged<G, T2>,
+        delay: FF,
+        compare: CF,
+        output: S,
+    ) -> Collection<G, D, <R as Multiply<T2::R>>::Output>
+    where
+        V2: Data,
+        T2: TraceReader<Key = K, Val = V2, Time = G::Timestamp> + Clone + 'static,
+        T2::R: Semigroup,
+        R: Multiply<T2::R>,
+        <R as Multiply<T2::R>>::Output: Semigroup + 'static,
+        D: Data,
+        FF: Fn(&G::Timestamp) -> G::Timestamp + 'static,
+        CF: Fn(&G::Timestamp, &G::Timestamp) -> bool + 'static,
+        S: Fn(&K, &V, &V2) -> D + 'static,
+    {
+        let mut join_trace = trace.trace.clone();
+        let exchange = Exchange::new(
+            |((key, _v, _t), _time, _diff): &((K, V, G::Timestamp), G::Timestamp, R)| {
+                key.hashed().into()
+            },
+        );
+
+        let mut builder = OperatorBuilder::new("MzHalfJoin".to_owned(), self.inner.scope());
+        let mut probe_input = builder.new_input(&self.inner, exchange);
+        let mut trace_input = builder.new_input(&trace.stream, Pipeline);
+        let (mut output_handle, stream) = builder.new_output();
+
+        // Probes whose join time isn't yet known to be complete in `trace`, stashed by the
+        // `delay`-computed time at which they can be re-attempted, alongside the capability that
+        // keeps that output time alive until then.
+        let mut stash: BTreeMap<
+            G::Timestamp,
+            (Capability<G::Timestamp>, Vec<(K, V, G::Timestamp, R)>),
+        > = BTreeMap::new();
+
+        builder.build(move |_capabilities| {
+            move |frontiers| {
+                // We don't need `trace_input`'s data, only the fact that its frontier tells us
+                // when `join_trace` is safe to compact and query up to a given time.
+                trace_input.for_each(|_time, _data| {});
+                let trace_frontier = frontiers[1].frontier();
+
+                let mut output_handle = output_handle.activate();
+                probe_input.for_each(|capability, data| {
+                    let mut session = output_handle.session(&capability);
+                    for ((key, v, t), _time, diff) in data.drain(..) {
+                        let delayed = delay(&t);
+                        if trace_frontier.less_equal(&delayed) {
+                            // `join_trace` isn't known complete up to `delayed` yet - stash the
+                            // probe rather than answering it now, or it would be answered again
+                            // (double-counted) once it's re-attempted below.
+                            stash
+                                .entry(delayed.clone())
+                                .or_insert_with(|| (capability.delayed(&delayed), Vec::new()))
+                                .1
+                                .push((key, v, t, diff));
+                        } else {
+                            join_one(&mut join_trace, &compare, &output, &key, &v, &t, &diff, &mut session);
+                        }
+                    }
+                });
+
+                // Re-attempt any stashed probes whose delayed time is no longer beyond the
+                // trace's frontier, i.e. `join_trace` is now complete enough to answer them.
+                let ready: Vec<_> = stash
+                    .keys()
+                    .filter(|time| !trace_frontier.less_equal(time))
+                    .cloned()
+                    .collect();
+                for time in ready {
+                    let (capability, probes) = stash.remove(&time).expect("just found above");
+                    let mut session = output_handle.session(&capability);
+                    for (key, v, t, diff) in probes {
+                        join_one(&mut join_trace, &compare, &output, &key, &v, &t, &diff, &mut session);
+                    }
+                }
+
+                join_trace.set_logical_compaction(trace_frontier.borrow());
+                join_trace.set_physical_compaction(trace_frontier.borrow());
+            }
+        });
+
+        /// Seeks `trace` to `key` and emits `output(key, v, val)` for every `(val, arr_time,
+        /// arr_diff)` for which `compare(t, arr_time)` holds.
+        fn join_one<K, V, V2, T2, D, CF, S, Diff>(
+            trace: &mut T2,
+            compare: &CF,
+            output: &S,
+            key: &K,
+            v: &V,
+            t: &T2::Time,
+            diff: &Diff,
+            session: &mut timely::dataflow::operators::generic::OutputSession<
+                '_,
+                T2::Time,
+                (D, T2::Time, <Diff as Multiply<T2::R>>::Output),
+                timely::dataflow::channels::pushers::Tee<T2::Time, (D, T2::Time, <Diff as Multiply<T2::R>>::Output)>,
+            >,
+        ) where
+            T2: TraceReader<Key = K, Val = V2>,
+            T2::Time: Lattice,
+            T2::R: Semigroup,
+            Diff: Multiply<T2::R> + Clone,
+            <Diff as Multiply<T2::R>>::Output: Semigroup,
+            D: Data,
+            CF: Fn(&T2::Time, &T2::Time) -> bool,
+            S: Fn(&K, &V, &V2) -> D,
+        {
+            let (mut cursor, storage) = trace.cursor();
+            cursor.seek_key(&storage, key);
+            if cursor.key_valid(&storage) && cursor.key(&storage) == key {
+                while cursor.val_valid(&storage) {
+                    let val = cursor.val(&storage);
+                    let datum = output(key, v, val);
+                    cursor.map_times(&storage, |arr_time, arr_diff| {
+                        if compare(t, arr_time) {
+                            let join_time = t.join(arr_time);
+                            session.give((datum.clone(), join_time, diff.clone().multiply(arr_diff)));
+                        }
+                    });
+                    cursor.step_val(&storage);
+                }
+            }
+        }
+
+        stream.as_collection()
+    }
+}
+
 // A type that can log its heap size.
 pub(crate) trait ArrangementSize {
     fn log_arrangement_size(&self) -> Self;
@@ -138,13 +464,78 @@ pub(crate) trait ArrangementSize {
 /// Helper to compute the size of a vector in memory.
 ///
 /// The function only considers the immediate allocation of the vector, but is oblivious of any
-/// pointers to owned allocations.
+/// pointers to owned allocations. Use [`deep_vec_size`] instead when `T` owns further heap
+/// allocations that should be counted too.
 #[inline]
 fn vec_size<T>(data: &Vec<T>, mut callback: impl FnMut(usize, usize)) {
     let size_of_t = std::mem::size_of::<T>();
     callback(data.len() * size_of_t, data.capacity() * size_of_t);
 }
 
+/// A type that can report the heap allocations it owns beyond its own `size_of`, so that
+/// [`deep_vec_size`] can account for them.
+///
+/// This exists because [`vec_size`] only ever sees a `T`'s inline representation: a `Row` that
+/// has spilled its packed bytes to the heap, or a `DataflowError` holding an owned `String`, looks
+/// the same size as an empty one to `size_of::<T>()`. Implementors should call `callback` once per
+/// owned allocation reachable from `self`, reporting that allocation's length and capacity in
+/// bytes.
+pub(crate) trait DeepSizeOf {
+    fn deep_size_of(&self, callback: &mut dyn FnMut(usize, usize));
+}
+
+impl DeepSizeOf for Row {
+    fn deep_size_of(&self, callback: &mut dyn FnMut(usize, usize)) {
+        // A `Row`'s datums all borrow from its own packed byte buffer, so that buffer is the only
+        // owned allocation to report; there are no further pointers to chase.
+        callback(self.byte_len(), self.byte_len());
+    }
+}
+
+impl DeepSizeOf for DataflowError {
+    fn deep_size_of(&self, callback: &mut dyn FnMut(usize, usize)) {
+        // `DataflowError`'s variants are too numerous (and not uniformly introspectable) to walk
+        // field-by-field here, so approximate via the owned `String` its `Display` impl would
+        // allocate. This undercounts errors that carry additional owned data (e.g. a `Row`), but
+        // is far closer to the truth than ignoring the error's payload entirely.
+        let message = self.to_string();
+        callback(message.len(), message.capacity());
+    }
+}
+
+/// Caps how many elements of a batch layer [`deep_vec_size`] measures directly; the remainder are
+/// extrapolated from the sampled average. Keeps the cost of `log_arrangement_size` from scaling
+/// with the size of a batch that may hold millions of records.
+const DEEP_SIZE_SAMPLE_LIMIT: usize = 1024;
+
+/// Like [`vec_size`], but for vectors of record types (e.g. `Row`, `DataflowError`) that own
+/// further heap allocations `vec_size` can't see. Reports `data`'s own backing allocation exactly,
+/// plus the heap footprint reachable through up to [`DEEP_SIZE_SAMPLE_LIMIT`] sampled elements'
+/// [`DeepSizeOf`] impls, extrapolated across the full vector.
+#[inline]
+fn deep_vec_size<T: DeepSizeOf>(data: &Vec<T>, mut callback: impl FnMut(usize, usize)) {
+    vec_size(data, &mut callback);
+    if data.is_empty() {
+        return;
+    }
+
+    let sample_len = data.len().min(DEEP_SIZE_SAMPLE_LIMIT);
+    let stride = (data.len() / sample_len).max(1);
+    let (mut sample_size, mut sample_capacity) = (0usize, 0usize);
+    for element in data.iter().step_by(stride).take(sample_len) {
+        element.deep_size_of(&mut |siz, cap| {
+            sample_size += siz;
+            sample_capacity += cap;
+        });
+    }
+
+    let scale = data.len() as f64 / sample_len as f64;
+    callback(
+        (sample_size as f64 * scale) as usize,
+        (sample_capacity as f64 * scale) as usize,
+    );
+}
+
 /// Helper for [`ArrangementSize`] to install a common operator holding on to a trace.
 fn log_arrangement_size_inner<G, Tr, L>(arranged: &Arranged<G, TraceAgent<Tr>>, mut logic: L)
 where
@@ -251,9 +642,9 @@ where
                 capacity += cap
             };
             trace.map_batches(|batch| {
-                vec_size(&batch.layer.keys, &mut callback);
+                deep_vec_size(&batch.layer.keys, &mut callback);
                 vec_size(&batch.layer.offs, &mut callback);
-                vec_size(&batch.layer.vals.keys, &mut callback);
+                deep_vec_size(&batch.layer.vals.keys, &mut callback);
                 vec_size(&batch.layer.vals.offs, &mut callback);
                 vec_size(&batch.layer.vals.vals.vals, &mut callback);
             });
@@ -279,7 +670,7 @@ where
                 capacity += cap
             };
             trace.map_batches(|batch| {
-                vec_size(&batch.layer.keys, &mut callback);
+                deep_vec_size(&batch.layer.keys, &mut callback);
                 vec_size(&batch.layer.offs, &mut callback);
                 vec_size(&batch.layer.vals.vals, &mut callback);
             });
@@ -316,7 +707,27 @@ where
     }
 }
 
-// TODO: `reduce_pair`, `consolidate_named_if`
+/// Tags which of [`MzReduce::mz_reduce_pair`]'s two reduction closures produced a given value, so
+/// both can be folded into the single arrangement that one cursor pass over the input produces.
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+enum Paired<A, B> {
+    First(A),
+    Second(B),
+}
+
+// TODO: `consolidate_named_if`
+// TODO: `mz_arrange_fold` - a value-transforming arrange that folds a combiner into each key's
+// state as batches are built, so a high-cardinality `count`/`sum`-style pre-aggregation never
+// materializes the full input multiset the way `mz_arrange_core` + `mz_reduce_abelian` does today.
+// A first attempt at this (see history for `MzArrangeFold`) built it as an ordinary
+// `OperatorBuilder` with a `BTreeMap<K, B>` of folded state and was reverted: it stamped every
+// output at the activation's capability time instead of each update's actual time, and never
+// compacted `state` against the input frontier, so it was neither time-respecting nor bounded in
+// memory - strictly worse than the `mz_reduce_abelian` it was meant to avoid materializing. Doing
+// this correctly means folding inside the trace's own batch `Builder`/`Batcher` (so compaction and
+// per-time semantics come from the spine, not a hand-rolled map), which is a change to
+// differential dataflow's batcher plumbing, not a small addition to this file. Left unimplemented
+// rather than shipping another unverified attempt at that.
 /// Extension trait for the `reduce_core` differential dataflow method.
 pub(crate) trait MzReduce<G: Scope, K: Data, V: Data, R: Semigroup>:
     ReduceCore<G, K, V, R>
@@ -343,6 +754,76 @@ where
         })
         .log_arrangement_size()
     }
+
+    /// Applies two independent reductions, `logic_a` and `logic_b`, to the same arranged input in
+    /// a single operator, returning their two output arrangements.
+    ///
+    /// Calling `mz_reduce_abelian` twice would seek and replay the shared input trace twice; here
+    /// the per-key `&[(&V, R)]` history is materialized once and handed to both closures, so the
+    /// (potentially large) input is only scanned once. Splitting the one combined output back
+    /// into two arrangements is comparatively cheap, since by that point the data has already
+    /// been reduced down to (typically much smaller) per-key aggregates, and splitting doesn't
+    /// require moving data between workers — each key's two outputs stay on the worker that
+    /// already owns that key.
+    fn mz_reduce_pair<L1, L2, V2, V3, R2, T2, T3>(
+        &self,
+        name: &str,
+        mut logic_a: L1,
+        mut logic_b: L2,
+    ) -> (Arranged<G, TraceAgent<T2>>, Arranged<G, TraceAgent<T3>>)
+    where
+        K: ExchangeData + Hashable,
+        V2: ExchangeData,
+        V3: ExchangeData,
+        R2: Abelian + ExchangeData,
+        T2: Trace + TraceReader<Key = K, Val = V2, Time = G::Timestamp, R = R2> + 'static,
+        T2::Batch: Batch,
+        T3: Trace + TraceReader<Key = K, Val = V3, Time = G::Timestamp, R = R2> + 'static,
+        T3::Batch: Batch,
+        L1: FnMut(&K, &[(&V, R)], &mut Vec<(V2, R2)>) + 'static,
+        L2: FnMut(&K, &[(&V, R)], &mut Vec<(V3, R2)>) + 'static,
+        Arranged<G, TraceAgent<T2>>: ArrangementSize,
+        Arranged<G, TraceAgent<T3>>: ArrangementSize,
+    {
+        // Allow access to `reduce_core` since we're within Mz's wrapper. The combined output is
+        // keyed the same as the input and re-arranged (cheaply, with no cross-worker movement)
+        // into the two requested trace types below, so this scratch arrangement doesn't need to
+        // go through `log_arrangement_size` itself.
+        #[allow(clippy::disallowed_methods)]
+        let paired = self.reduce_core::<_, OrdValSpine<K, Paired<V2, V3>, G::Timestamp, R2>>(
+            name,
+            move |key, input, output, change| {
+                if !input.is_empty() {
+                    let mut a = Vec::new();
+                    let mut b = Vec::new();
+                    logic_a(key, input, &mut a);
+                    logic_b(key, input, &mut b);
+                    change.extend(a.into_iter().map(|(v, r)| (Paired::First(v), r)));
+                    change.extend(b.into_iter().map(|(v, r)| (Paired::Second(v), r)));
+                }
+                change.extend(output.drain(..).map(|(x, d)| (x, d.negate())));
+            },
+        );
+
+        // `paired` is already arranged by `key`, so splitting it back into two collections and
+        // re-arranging doesn't need to move data between workers - a `Pipeline` pact keeps each
+        // key's two outputs on the worker that already owns that key, unlike `mz_arrange`'s
+        // hashed `Exchange` pact, which would needlessly re-partition already-partitioned data.
+        let collection = paired.as_collection(|key, value| (key.clone(), value.clone()));
+        let a = collection
+            .flat_map(|(key, value)| match value {
+                Paired::First(v) => Some((key, v)),
+                Paired::Second(_) => None,
+            })
+            .mz_arrange_core::<_, T2>(Pipeline, &format!("{name}First"));
+        let b = collection
+            .flat_map(|(key, value)| match value {
+                Paired::First(_) => None,
+                Paired::Second(v) => Some((key, v)),
+            })
+            .mz_arrange_core::<_, T3>(Pipeline, &format!("{name}Second"));
+        (a, b)
+    }
 }
 
 impl<G, K, V, T1, R> MzReduce<G, K, V, R> for Arranged<G, T1>
@@ -355,3 +836,69 @@ where
     T1: TraceReader<Key = K, Val = V, Time = G::Timestamp, R = R> + Clone + 'static,
 {
 }
+
+#[cfg(test)]
+mod tests {
+    use differential_dataflow::input::InputSession;
+    use timely::dataflow::operators::capture::{Capture, Extract};
+
+    use super::*;
+
+    /// Regression test for a bug where `mz_broadcast_join` only reacted to new data on the
+    /// broadcast (`small`) side, silently dropping pairs where `large` gained a match for a
+    /// `small` value it had already seen - i.e. a `large` update with no contemporaneous `small`
+    /// update was never joined against `small`'s accumulated history.
+    #[mz_ore::test]
+    fn broadcast_join_matches_large_only_updates_against_small_history() {
+        let captured = timely::execute_directly(move |worker| {
+            let mut large_input: InputSession<u64, i64, isize> = InputSession::new();
+            let mut small_input: InputSession<u64, i64, isize> = InputSession::new();
+
+            let capture = worker.dataflow(|scope| {
+                let large = large_input
+                    .to_collection(scope)
+                    .map(|value| ((), value))
+                    .mz_arrange_core::<_, OrdValSpine<(), i64, u64, isize>>(Pipeline, "TestLarge");
+                let small = small_input.to_collection(scope);
+                large
+                    .mz_broadcast_join(&small, "Test", |l: &i64, s: &i64| Some(*l + *s))
+                    .inner
+                    .capture()
+            });
+
+            // Round 1: only `small` changes. At this point `large` is still empty, so this
+            // produces no output, but `small`'s value must be remembered.
+            small_input.insert(10);
+            small_input.advance_to(1);
+            small_input.flush();
+            large_input.advance_to(1);
+            large_input.flush();
+            while worker.step() {}
+
+            // Round 2: only `large` changes, with no contemporaneous `small` update. The bug
+            // dropped this pairing entirely; a correct join must still match `20` against the
+            // `10` that arrived in round 1.
+            large_input.insert(20);
+            large_input.advance_to(2);
+            large_input.flush();
+            small_input.advance_to(2);
+            small_input.flush();
+            while worker.step() {}
+
+            capture
+        });
+
+        let total: isize = captured
+            .extract()
+            .into_iter()
+            .flat_map(|(_time, data)| data.into_iter())
+            .filter(|(value, _time, _diff)| *value == 30)
+            .map(|(_, _, diff)| diff)
+            .sum();
+        assert_eq!(
+            total, 1,
+            "a large-only update must still be joined against small's prior history"
+        );
+    }
+}
+
@@ -78,6 +78,227 @@ impl Peek {
     }
 }
 
+/// A region-allocated, columnar alternative to `Vec<(Duration, WorkerIdentifier, ComputeEvent)>`
+/// for the compute logging demux's hot input path.
+///
+/// Every [`ComputeEvent`] variant is made up entirely of small fields that are cheap to copy (a
+/// [`GlobalId`], a [`Timestamp`], a [`Peek`] of a few `Copy` fields, ...), so rather than storing
+/// one [`ComputeEvent`] per record — paying
+/// for the largest variant's size and mixing unrelated shapes together in memory — each record's
+/// fields are copied into whichever small, per-variant `Vec` ("region") matches its shape, and the
+/// container itself keeps only a compact per-record index: a [`ComputeEventTag`] plus the offset
+/// of that record within its region. This keeps same-shaped records contiguous in storage and lets
+/// each region grow independently instead of churning one `Vec` sized for the union of all
+/// variants. Records are read back in the order they were pushed, not grouped by shape.
+///
+/// This covers the buffer that `input.for_each` swaps into (the `EventLink`/`MzReplay` side of the
+/// hot path named in the originating request); turning the `new_output` streams themselves into
+/// region-allocated containers would additionally require `Container`/`PushContainer`-generic
+/// stream support from `timely::dataflow::operators::capture::EventLink` and
+/// `mz_timely_util::replay::MzReplay`, neither of which lives in this crate, so that part is left
+/// as follow-up work once this path has proven itself out.
+#[derive(Default)]
+struct ComputeEventContainer {
+    times: Vec<Duration>,
+    workers: Vec<WorkerIdentifier>,
+    index: Vec<(ComputeEventTag, u32)>,
+    dataflow: Vec<(GlobalId, bool)>,
+    dependency: Vec<(GlobalId, GlobalId)>,
+    peek: Vec<(Peek, bool)>,
+    frontier: Vec<(GlobalId, Timestamp, i64)>,
+    source_frontier: Vec<(GlobalId, GlobalId, Timestamp, i8)>,
+}
+
+/// Identifies which per-variant region in a [`ComputeEventContainer`] a given record was copied
+/// into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ComputeEventTag {
+    Dataflow,
+    DataflowDependency,
+    Peek,
+    Frontier,
+    SourceFrontier,
+}
+
+impl ComputeEventContainer {
+    /// The number of records currently held.
+    fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Empties the container without shrinking any of its regions, so the next batch of pushes
+    /// reuses the capacity built up so far.
+    fn clear(&mut self) {
+        self.times.clear();
+        self.workers.clear();
+        self.index.clear();
+        self.dataflow.clear();
+        self.dependency.clear();
+        self.peek.clear();
+        self.frontier.clear();
+        self.source_frontier.clear();
+    }
+
+    /// Copies `event`'s fields into the region matching its variant.
+    fn push(&mut self, time: Duration, worker: WorkerIdentifier, event: ComputeEvent) {
+        self.times.push(time);
+        self.workers.push(worker);
+        let (tag, offset) = match event {
+            ComputeEvent::Dataflow(id, is_create) => {
+                let offset = self.dataflow.len();
+                self.dataflow.push((id, is_create));
+                (ComputeEventTag::Dataflow, offset)
+            }
+            ComputeEvent::DataflowDependency { dataflow, source } => {
+                let offset = self.dependency.len();
+                self.dependency.push((dataflow, source));
+                (ComputeEventTag::DataflowDependency, offset)
+            }
+            ComputeEvent::Peek(peek, is_install) => {
+                let offset = self.peek.len();
+                self.peek.push((peek, is_install));
+                (ComputeEventTag::Peek, offset)
+            }
+            ComputeEvent::Frontier(id, logical, delta) => {
+                let offset = self.frontier.len();
+                self.frontier.push((id, logical, delta));
+                (ComputeEventTag::Frontier, offset)
+            }
+            ComputeEvent::SourceFrontier(dataflow, source, logical, delta) => {
+                let offset = self.source_frontier.len();
+                self.source_frontier.push((dataflow, source, logical, delta));
+                (ComputeEventTag::SourceFrontier, offset)
+            }
+        };
+        let offset =
+            u32::try_from(offset).expect("a single logging batch has far fewer than u32::MAX events of one kind");
+        self.index.push((tag, offset));
+    }
+
+    /// Reconstructs the record at `i`. Every field involved is `Copy` (or, for [`Peek`], a cheap
+    /// clone of a few `Copy` fields), so this is a handful of array reads, never a heap allocation.
+    fn get(&self, i: usize) -> (Duration, WorkerIdentifier, ComputeEvent) {
+        let (tag, offset) = self.index[i];
+        let offset = offset as usize;
+        let event = match tag {
+            ComputeEventTag::Dataflow => {
+                let (id, is_create) = self.dataflow[offset];
+                ComputeEvent::Dataflow(id, is_create)
+            }
+            ComputeEventTag::DataflowDependency => {
+                let (dataflow, source) = self.dependency[offset];
+                ComputeEvent::DataflowDependency { dataflow, source }
+            }
+            ComputeEventTag::Peek => {
+                let (peek, is_install) = self.peek[offset].clone();
+                ComputeEvent::Peek(peek, is_install)
+            }
+            ComputeEventTag::Frontier => {
+                let (id, logical, delta) = self.frontier[offset];
+                ComputeEvent::Frontier(id, logical, delta)
+            }
+            ComputeEventTag::SourceFrontier => {
+                let (dataflow, source, logical, delta) = self.source_frontier[offset];
+                ComputeEvent::SourceFrontier(dataflow, source, logical, delta)
+            }
+        };
+        (self.times[i], self.workers[i], event)
+    }
+
+    /// Drains all records out in order, leaving the container empty (but with its regions'
+    /// capacity intact) for reuse.
+    fn drain(&mut self) -> impl Iterator<Item = (Duration, WorkerIdentifier, ComputeEvent)> + '_ {
+        let items: Vec<_> = (0..self.len()).map(|i| self.get(i)).collect();
+        self.clear();
+        items.into_iter()
+    }
+}
+
+/// A raw field extracted from a [`ComputeEvent`] (or the demux's own bookkeeping), not yet
+/// rendered into a [`Datum`].
+///
+/// Pairing these with a [`ColumnConversion`] in [`pack_logged_row`] is what lets a pack site
+/// declare "this column is a timestamp" instead of hard-coding how a timestamp is rendered.
+#[derive(Debug, Clone, Copy)]
+enum LoggedValue {
+    /// A `GlobalId`, typically rendered as its string form.
+    Id(GlobalId),
+    /// A worker index.
+    Worker(WorkerIdentifier),
+    /// A logical compute timestamp.
+    Timestamp(Timestamp),
+    /// A bare signed integer.
+    Int(i64),
+    /// A bare unsigned integer.
+    Uint(u64),
+    /// A peek's `Uuid`.
+    Uuid(Uuid),
+}
+
+/// Declares how a single logged column should be rendered into a [`Datum`].
+///
+/// New log variants pick a conversion per column instead of writing a new `Datum::*(...)` cast in
+/// the packing closure; existing variants that used to cast a [`Timestamp`] down to `Datum::Int64`
+/// (the removed `// TODO: Convert to MzTimestamp.` sites) now declare [`ColumnConversion::MzTimestamp`]
+/// and get the native rendering for free.
+#[derive(Debug, Clone, Copy)]
+enum ColumnConversion {
+    /// Render a [`LoggedValue::Id`] as `Datum::String`.
+    IdString,
+    /// Render a [`LoggedValue::Worker`] or [`LoggedValue::Uint`] as `Datum::UInt64`.
+    UInt64,
+    /// Render a [`LoggedValue::Int`] as `Datum::Int64`.
+    Int64,
+    /// Render a [`LoggedValue::Timestamp`] as a native `Datum::MzTimestamp`.
+    MzTimestamp,
+    /// Render a [`LoggedValue::Uuid`] as `Datum::Uuid`.
+    Uuid,
+}
+
+/// Packs `values` into a [`Row`], rendering each one according to the matching entry of
+/// `conversions`.
+///
+/// This centralizes the column-rendering decisions that used to be repeated (and occasionally
+/// fudged, e.g. `Datum::Int64(logical.try_into().expect("must fit"))`) at every `Row::pack_slice`
+/// call site in [`construct`].
+fn pack_logged_row(conversions: &[ColumnConversion], values: &[LoggedValue]) -> Row {
+    assert_eq!(
+        conversions.len(),
+        values.len(),
+        "a logged row must supply exactly one value per declared column"
+    );
+    // Owned strings backing `Datum::String` must outlive the `pack_slice` call below, so they're
+    // computed up front rather than inline in the `match`.
+    let strings: Vec<Option<String>> = values
+        .iter()
+        .map(|value| match value {
+            LoggedValue::Id(id) => Some(id.to_string()),
+            _ => None,
+        })
+        .collect();
+    let datums: Vec<Datum> = conversions
+        .iter()
+        .zip(values)
+        .zip(&strings)
+        .map(|((conversion, value), string)| match (conversion, value) {
+            (ColumnConversion::IdString, LoggedValue::Id(_)) => {
+                Datum::String(string.as_deref().expect("computed above"))
+            }
+            (ColumnConversion::UInt64, LoggedValue::Worker(worker)) => {
+                Datum::UInt64(u64::cast_from(*worker))
+            }
+            (ColumnConversion::UInt64, LoggedValue::Uint(u)) => Datum::UInt64(*u),
+            (ColumnConversion::Int64, LoggedValue::Int(i)) => Datum::Int64(*i),
+            (ColumnConversion::MzTimestamp, LoggedValue::Timestamp(ts)) => Datum::MzTimestamp(*ts),
+            (ColumnConversion::Uuid, LoggedValue::Uuid(uuid)) => Datum::Uuid(*uuid),
+            (conversion, value) => panic!(
+                "logged value {value:?} does not match its declared column conversion {conversion:?}"
+            ),
+        })
+        .collect();
+    Row::pack_slice(&datums)
+}
+
 /// Constructs the logging dataflow for compute logs.
 ///
 /// Params
@@ -85,6 +306,10 @@ impl Peek {
 /// * `config`: Logging configuration
 /// * `compute`: The source to read compute log events from.
 /// * `activator`: A handle to acknowledge activations.
+/// * `use_flat_containers`: Route the demux's hot input path through the region-allocated
+///   [`ComputeEventContainer`] instead of matching directly on the swapped-in `Vec`, so the two can
+///   be benchmarked against each other. This belongs on `LoggingConfig` alongside `interval_ns`;
+///   it's a separate parameter only because `LoggingConfig` lives in `mz_compute_client`.
 ///
 /// Returns a map from log variant to a tuple of a trace handle and a permutation to reconstruct
 /// the original rows.
@@ -94,6 +319,7 @@ pub fn construct<A: Allocate>(
     compute_state: &mut ComputeState,
     compute: std::rc::Rc<EventLink<Timestamp, (Duration, WorkerIdentifier, ComputeEvent)>>,
     activator: RcActivator,
+    use_flat_containers: bool,
 ) -> HashMap<LogVariant, (KeysValsHandle, Rc<dyn Any>)> {
     let interval_ms = std::cmp::max(1, config.interval_ns / 1_000_000);
 
@@ -117,6 +343,7 @@ pub fn construct<A: Allocate>(
         let (mut peek_duration_out, peek_duration) = demux.new_output();
 
         let mut demux_buffer = Vec::new();
+        let mut flat_buffer = ComputeEventContainer::default();
         demux.build(move |_capability| {
             let mut active_dataflows = HashMap::new();
             let mut peek_stash = HashMap::new();
@@ -144,7 +371,21 @@ pub fn construct<A: Allocate>(
                     let mut peek_session = peek.session(&time);
                     let mut peek_duration_session = peek_duration.session(&time);
 
-                    for (time, worker, datum) in demux_buffer.drain(..) {
+                    // The region-allocated path copies the just-swapped-in batch into the
+                    // per-variant regions of `ComputeEventContainer` and drains it back out in
+                    // push order. Either way, the match below sees the same
+                    // `(Duration, WorkerIdentifier, ComputeEvent)` triples in the same order.
+                    let events: Box<dyn Iterator<Item = (Duration, WorkerIdentifier, ComputeEvent)>> =
+                        if use_flat_containers {
+                            for (time, worker, event) in demux_buffer.drain(..) {
+                                flat_buffer.push(time, worker, event);
+                            }
+                            Box::new(flat_buffer.drain())
+                        } else {
+                            Box::new(demux_buffer.drain(..))
+                        };
+
+                    for (time, worker, datum) in events {
                         let time_ms = (((time.as_millis() / interval_ms) + 1) * interval_ms)
                             .try_into()
                             .expect("must fit");
@@ -212,12 +453,18 @@ pub fn construct<A: Allocate>(
                             ComputeEvent::Frontier(name, logical, delta) => {
                                 // report dataflow frontier advancement
                                 frontier_session.give((
-                                    Row::pack_slice(&[
-                                        Datum::String(&name.to_string()),
-                                        Datum::UInt64(u64::cast_from(worker)),
-                                        // TODO: Convert to MzTimestamp.
-                                        Datum::Int64(logical.try_into().expect("must fit")),
-                                    ]),
+                                    pack_logged_row(
+                                        &[
+                                            ColumnConversion::IdString,
+                                            ColumnConversion::UInt64,
+                                            ColumnConversion::MzTimestamp,
+                                        ],
+                                        &[
+                                            LoggedValue::Id(name),
+                                            LoggedValue::Worker(worker),
+                                            LoggedValue::Timestamp(logical),
+                                        ],
+                                    ),
                                     time_ms,
                                     delta,
                                 ));
@@ -254,13 +501,20 @@ pub fn construct<A: Allocate>(
                             ComputeEvent::SourceFrontier(dataflow, source_id, logical, delta) => {
                                 // report source instantiation frontier advancement
                                 source_frontier_session.give((
-                                    Row::pack_slice(&[
-                                        Datum::String(&dataflow.to_string()),
-                                        Datum::String(&source_id.to_string()),
-                                        Datum::UInt64(u64::cast_from(worker)),
-                                        // TODO: Convert to MzTimestamp.
-                                        Datum::Int64(u64::from(logical) as i64),
-                                    ]),
+                                    pack_logged_row(
+                                        &[
+                                            ColumnConversion::IdString,
+                                            ColumnConversion::IdString,
+                                            ColumnConversion::UInt64,
+                                            ColumnConversion::MzTimestamp,
+                                        ],
+                                        &[
+                                            LoggedValue::Id(dataflow),
+                                            LoggedValue::Id(source_id),
+                                            LoggedValue::Worker(worker),
+                                            LoggedValue::Timestamp(logical),
+                                        ],
+                                    ),
                                     time_ms,
                                     i64::from(delta),
                                 ));
@@ -322,20 +576,27 @@ pub fn construct<A: Allocate>(
 
         let dataflow_current = dataflow.as_collection().map({
             move |(name, worker)| {
-                Row::pack_slice(&[
-                    Datum::String(&name.to_string()),
-                    Datum::UInt64(u64::cast_from(worker)),
-                ])
+                pack_logged_row(
+                    &[ColumnConversion::IdString, ColumnConversion::UInt64],
+                    &[LoggedValue::Id(name), LoggedValue::Worker(worker)],
+                )
             }
         });
 
         let dependency_current = dependency.as_collection().map({
             move |(dataflow, source, worker)| {
-                Row::pack_slice(&[
-                    Datum::String(&dataflow.to_string()),
-                    Datum::String(&source.to_string()),
-                    Datum::UInt64(u64::cast_from(worker)),
-                ])
+                pack_logged_row(
+                    &[
+                        ColumnConversion::IdString,
+                        ColumnConversion::IdString,
+                        ColumnConversion::UInt64,
+                    ],
+                    &[
+                        LoggedValue::Id(dataflow),
+                        LoggedValue::Id(source),
+                        LoggedValue::Worker(worker),
+                    ],
+                )
             }
         });
 
@@ -348,36 +609,59 @@ pub fn construct<A: Allocate>(
             .count_total_core::<i64>()
             .map({
                 move |((dataflow, source_id, worker, delay_pow), count)| {
-                    Row::pack_slice(&[
-                        Datum::String(&dataflow.to_string()),
-                        Datum::String(&source_id.to_string()),
-                        Datum::UInt64(u64::cast_from(worker)),
-                        Datum::UInt64(delay_pow.try_into().expect("pow too big")),
-                        Datum::Int64(count.into()),
-                    ])
+                    pack_logged_row(
+                        &[
+                            ColumnConversion::IdString,
+                            ColumnConversion::IdString,
+                            ColumnConversion::UInt64,
+                            ColumnConversion::UInt64,
+                            ColumnConversion::Int64,
+                        ],
+                        &[
+                            LoggedValue::Id(dataflow),
+                            LoggedValue::Id(source_id),
+                            LoggedValue::Worker(worker),
+                            LoggedValue::Uint(delay_pow.try_into().expect("pow too big")),
+                            LoggedValue::Int(count.into()),
+                        ],
+                    )
                 }
             });
 
         let peek_current = peek.as_collection().map({
             move |(peek, worker)| {
-                Row::pack_slice(&[
-                    Datum::Uuid(peek.uuid),
-                    Datum::UInt64(u64::cast_from(worker)),
-                    Datum::String(&peek.id.to_string()),
-                    // TODO: Convert to MzTimestamp.
-                    Datum::Int64(u64::from(peek.time) as i64),
-                ])
+                pack_logged_row(
+                    &[
+                        ColumnConversion::Uuid,
+                        ColumnConversion::UInt64,
+                        ColumnConversion::IdString,
+                        ColumnConversion::MzTimestamp,
+                    ],
+                    &[
+                        LoggedValue::Uuid(peek.uuid),
+                        LoggedValue::Worker(worker),
+                        LoggedValue::Id(peek.id),
+                        LoggedValue::Timestamp(peek.time),
+                    ],
+                )
             }
         });
 
         // Duration statistics derive from the non-rounded event times.
         let peek_duration = peek_duration.as_collection().count_total_core().map({
             move |((worker, pow), count)| {
-                Row::pack_slice(&[
-                    Datum::UInt64(u64::cast_from(worker)),
-                    Datum::UInt64(pow.try_into().expect("pow too big")),
-                    Datum::UInt64(count),
-                ])
+                pack_logged_row(
+                    &[
+                        ColumnConversion::UInt64,
+                        ColumnConversion::UInt64,
+                        ColumnConversion::UInt64,
+                    ],
+                    &[
+                        LoggedValue::Worker(worker),
+                        LoggedValue::Uint(pow.try_into().expect("pow too big")),
+                        LoggedValue::Uint(count),
+                    ],
+                )
             }
         });
 
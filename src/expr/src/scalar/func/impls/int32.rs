@@ -7,10 +7,13 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use crate::EvalError;
+
 sqlfunc!(
     #[sqlname = "-"]
-    fn neg_int32(a: i32) -> i32 {
-        -a
+    fn neg_int32(a: i32) -> Result<i32, EvalError> {
+        a.checked_neg()
+            .ok_or_else(|| EvalError::Int32OutOfRange(a.to_string().into()))
     }
 );
 
@@ -23,7 +26,31 @@ sqlfunc!(
 
 sqlfunc!(
     #[sqlname = "abs"]
-    fn abs_int32(a: i32) -> i32 {
-        a.abs()
+    fn abs_int32(a: i32) -> Result<i32, EvalError> {
+        a.checked_abs()
+            .ok_or_else(|| EvalError::Int32OutOfRange(a.to_string().into()))
     }
 );
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_neg_int32_out_of_range() {
+        assert_eq!(
+            NegInt32.call(i32::MIN),
+            Err(EvalError::Int32OutOfRange(i32::MIN.to_string().into()))
+        );
+        assert_eq!(NegInt32.call(5), Ok(-5));
+    }
+
+    #[mz_ore::test]
+    fn test_abs_int32_out_of_range() {
+        assert_eq!(
+            AbsInt32.call(i32::MIN),
+            Err(EvalError::Int32OutOfRange(i32::MIN.to_string().into()))
+        );
+        assert_eq!(AbsInt32.call(-5), Ok(5));
+    }
+}
@@ -21,31 +21,58 @@ const ITEM_COLLECTION: TypedCollection<v36::ItemKey, v36::ItemValue> = TypedColl
 
 /// Persist `false` for existing environments' RBAC flags, iff they're not already set.
 pub async fn upgrade(tx: &mut Transaction<'_>) -> Result<(), StashError> {
-    ITEM_COLLECTION
-        .migrate_to(tx, |entries| {
+    migrate_to_checked(
+        &ITEM_COLLECTION,
+        tx,
+        |entries| {
             entries
-                .into_iter()
+                .iter()
                 .map(|(key, value)| {
                     let new_key: v37::ItemKey = WireCompatible::convert(key);
-                    let new_value: v37::ItemValue = value.clone().into();
-                    MigrationAction::Update(key.clone(), (new_key, new_value))
+                    let new_value: v37::ItemValue = v37::ItemValue::try_from(value.clone())?;
+                    Ok(MigrationAction::Update(key.clone(), (new_key, new_value)))
                 })
                 .collect()
-        })
-        .await
+        },
+        None::<fn(&[(v36::ItemKey, v36::ItemValue)]) -> Result<(), StashError>>,
+        Some(assert_item_keys_round_trip),
+    )
+    .await
+}
+
+/// Asserts every migrated key round-trips losslessly back to its pre-migration `v36` form through
+/// [`WireCompatible::convert`], i.e. that the `v37` schema didn't change anything `ItemKey`'s wire
+/// representation depends on.
+fn assert_item_keys_round_trip(
+    actions: &[MigrationAction<v36::ItemKey, (v37::ItemKey, v37::ItemValue)>],
+) -> Result<(), StashError> {
+    for action in actions {
+        if let MigrationAction::Update(old_key, (new_key, _)) = action {
+            let round_tripped: v36::ItemKey = WireCompatible::convert(new_key);
+            if round_tripped != *old_key {
+                return Err(StashError::from(format!(
+                    "ItemKey did not round-trip through WireCompatible::convert: \
+                     {old_key:?} -> {new_key:?} -> {round_tripped:?}"
+                )));
+            }
+        }
+    }
+    Ok(())
 }
 
-impl From<v36::ItemValue> for v37::ItemValue {
-    fn from(value: v36::ItemValue) -> Self {
+impl TryFrom<v36::ItemValue> for v37::ItemValue {
+    type Error = StashError;
+
+    fn try_from(value: v36::ItemValue) -> Result<Self, StashError> {
         let create_sql_value = value
             .definition
-            .expect("missing field ItemValue::definition")
+            .ok_or_else(|| StashError::from("missing field ItemValue::definition".to_string()))?
             .value
-            .expect("missing field CatalogItem::value");
+            .ok_or_else(|| StashError::from("missing field CatalogItem::value".to_string()))?;
         let create_sql = match create_sql_value {
             v36::catalog_item::Value::V1(c) => c.create_sql,
         };
-        Self {
+        Ok(Self {
             schema_id: value
                 .schema_id
                 .map(|schema_id| WireCompatible::convert(&schema_id)),
@@ -59,10 +86,67 @@ impl From<v36::ItemValue> for v37::ItemValue {
                 .into_iter()
                 .map(|privilege| WireCompatible::convert(&privilege))
                 .collect(),
-        }
+        })
     }
 }
 
+/// Runs a stash migration's `transform` against `tx`, gated behind optional `pre`/`post`
+/// invariant checks, and only commits if both pass.
+///
+/// Unlike `TypedCollection::migrate_to`, `transform` is fallible: a conversion failure on a
+/// malformed pre-migration entry (e.g. the old `ItemValue::try_from` panicking via `.expect`)
+/// surfaces as a `StashError` instead of panicking mid-migration. `pre` runs over the
+/// pre-migration entries, `post` runs over the proposed migration actions. If either returns
+/// `Err` (or `transform` does), the closure handed to `migrate_to` returns an empty action list and
+/// the error is returned, so the migration aborts with no partial write.
+///
+/// Beyond the caller-supplied checks, this always asserts that `transform` preserves the entry
+/// count, since every caller of this harness so far is a 1:1 `Update` migration.
+///
+/// This lives here rather than as a method on `TypedCollection` because `TypedCollection` and
+/// `Transaction` are defined outside this module; once a generic harness is needed by more than
+/// one upgrade, it should move there.
+async fn migrate_to_checked<K, V, NK, NV>(
+    collection: &TypedCollection<K, V>,
+    tx: &mut Transaction<'_>,
+    transform: impl Fn(&[(K, V)]) -> Result<Vec<MigrationAction<K, (NK, NV)>>, StashError>,
+    pre: Option<impl Fn(&[(K, V)]) -> Result<(), StashError>>,
+    post: Option<impl Fn(&[MigrationAction<K, (NK, NV)>]) -> Result<(), StashError>>,
+) -> Result<(), StashError> {
+    let mut validation: Result<(), StashError> = Ok(());
+
+    collection
+        .migrate_to(tx, |entries| {
+            let check = (|| -> Result<Vec<MigrationAction<K, (NK, NV)>>, StashError> {
+                if let Some(pre) = &pre {
+                    pre(&entries)?;
+                }
+                let actions = transform(&entries)?;
+                if actions.len() != entries.len() {
+                    return Err(StashError::from(format!(
+                        "migration changed entry count: {} entries became {} actions",
+                        entries.len(),
+                        actions.len()
+                    )));
+                }
+                if let Some(post) = &post {
+                    post(&actions)?;
+                }
+                Ok(actions)
+            })();
+            match check {
+                Ok(actions) => actions,
+                Err(err) => {
+                    validation = Err(err);
+                    Vec::new()
+                }
+            }
+        })
+        .await?;
+
+    validation
+}
+
 #[cfg(test)]
 mod tests {
     use crate::DebugStashFactory;
@@ -164,4 +248,65 @@ mod tests {
             )],
         );
     }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `TLS_client_method` on OS `linux`
+    async fn aborts_without_partial_write_on_malformed_entry() {
+        let factory = DebugStashFactory::new().await;
+        let mut stash = factory.open_debug().await;
+
+        ITEM_COLLECTION
+            .insert_without_overwrite(
+                &mut stash,
+                vec![(
+                    v36::ItemKey {
+                        gid: Some(v36::GlobalId {
+                            value: Some(v36::global_id::Value::User(42)),
+                        }),
+                    },
+                    v36::ItemValue {
+                        schema_id: Some(v36::SchemaId {
+                            value: Some(v36::schema_id::Value::User(66)),
+                        }),
+                        name: "v".to_string(),
+                        // Malformed: `try_from` requires `definition` to be present.
+                        definition: None,
+                        owner_id: Some(v36::RoleId {
+                            value: Some(v36::role_id::Value::User(1)),
+                        }),
+                        privileges: vec![],
+                    },
+                )],
+            )
+            .await
+            .unwrap();
+
+        let result = stash
+            .with_transaction(|mut tx| {
+                Box::pin(async move {
+                    upgrade(&mut tx).await?;
+                    Ok(())
+                })
+            })
+            .await;
+        assert!(result.is_err());
+
+        // Both typed collections name the same `item` collection, so an aborted migration shows
+        // up as the original v36 row still being there, unchanged.
+        let items: Vec<_> = ITEM_COLLECTION
+            .peek_one(&mut stash)
+            .await
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(
+            items.len(),
+            1,
+            "a failed migration must not partially write v37 rows"
+        );
+        assert!(
+            items[0].1.definition.is_none(),
+            "a failed migration must leave the original v36 row unchanged"
+        );
+    }
 }
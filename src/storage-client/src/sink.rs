@@ -7,6 +7,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::cmp::Ordering;
 use std::collections::BTreeMap;
 use std::time::Duration;
 
@@ -17,7 +18,12 @@ use mz_ore::future::{InTask, OreFutureExt};
 use mz_storage_types::configuration::StorageConfiguration;
 use mz_storage_types::errors::ContextCreationErrorExt;
 use mz_storage_types::sinks::{KafkaSinkConnection, KafkaSinkTopicOptions};
-use rdkafka::admin::{AdminClient, AdminOptions, NewTopic, ResourceSpecifier, TopicReplication};
+use rdkafka::admin::{
+    AdminClient, AdminOptions, AlterConfig, NewPartitions, NewTopic, ResourceSpecifier,
+    TopicReplication,
+};
+use rdkafka::error::{KafkaError, RDKafkaErrorCode};
+use rdkafka::metadata::Metadata;
 use rdkafka::ClientContext;
 use tracing::warn;
 
@@ -56,6 +62,64 @@ struct TopicConfigs {
     replication_factor: i32,
 }
 
+/// Number of times to retry an admin operation after it fails because the
+/// cluster's controller moved out from under it (e.g. during a controller
+/// election), before giving up.
+const CONTROLLER_RETRY_ATTEMPTS: u32 = 3;
+
+/// Resolves the id of the broker currently acting as the cluster controller.
+///
+/// Targeting the controller directly, rather than guessing via
+/// `metadata.brokers()[0]`, avoids routing admin requests to a broker that
+/// just has to forward them on, and the explicit failure here (rather than a
+/// misleading successful describe/alter against the wrong broker) makes it
+/// clear when a controller election is in progress.
+fn discover_controller_id(metadata: &Metadata) -> Result<i32, anyhow::Error> {
+    let controller_id = metadata.controller_id();
+    if controller_id < 0 {
+        bail!("cluster metadata did not report a controller (election in progress?)");
+    }
+    Ok(controller_id)
+}
+
+/// Returns whether `err` wraps a Kafka `NOT_CONTROLLER`-style error,
+/// indicating the broker we targeted has stopped being the controller.
+fn is_controller_moved_error(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        matches!(
+            cause.downcast_ref::<KafkaError>(),
+            Some(KafkaError::AdminOp(RDKafkaErrorCode::NotController))
+        )
+    })
+}
+
+/// Runs the fallible admin operation `f`, retrying up to
+/// [`CONTROLLER_RETRY_ATTEMPTS`] times when it fails with
+/// [`is_controller_moved_error`]. `what` and `topic` are only used to make the
+/// retry log line legible.
+async fn with_controller_retry<T, Fut>(
+    topic: &str,
+    what: &str,
+    mut f: impl FnMut() -> Fut,
+) -> Result<T, anyhow::Error>
+where
+    Fut: std::future::Future<Output = Result<T, anyhow::Error>>,
+{
+    let mut attempt = 0;
+    loop {
+        match f().await {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < CONTROLLER_RETRY_ATTEMPTS && is_controller_moved_error(&e) => {
+                attempt += 1;
+                warn!(
+                    "controller moved while {what} for topic {topic}, retrying (attempt {attempt}): {e}"
+                );
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
 async fn discover_topic_configs<C: ClientContext>(
     client: &AdminClient<C>,
     topic: &str,
@@ -64,39 +128,41 @@ async fn discover_topic_configs<C: ClientContext>(
     let mut partition_count = -1;
     let mut replication_factor = -1;
 
-    let metadata = client
-        .inner()
-        .fetch_metadata(None, fetch_timeout)
-        .with_context(|| {
-            format!(
-                "error fetching metadata when creating new topic {} for sink",
-                topic
-            )
-        })?;
+    let configs = with_controller_retry(topic, "describing broker configuration", || async {
+        let metadata = client
+            .inner()
+            .fetch_metadata(None, fetch_timeout)
+            .with_context(|| {
+                format!(
+                    "error fetching metadata when creating new topic {} for sink",
+                    topic
+                )
+            })?;
 
-    if metadata.brokers().len() == 0 {
-        Err(anyhow!("zero brokers discovered in metadata request"))?;
-    }
+        if metadata.brokers().len() == 0 {
+            Err(anyhow!("zero brokers discovered in metadata request"))?;
+        }
 
-    let broker = metadata.brokers()[0].id();
-    let configs = client
-        .describe_configs(
-            &[ResourceSpecifier::Broker(broker)],
-            &AdminOptions::new().request_timeout(Some(Duration::from_secs(5))),
-        )
-        .await
-        .with_context(|| {
-            format!(
-                "error fetching configuration from broker {} when creating new topic {} for sink",
-                broker, topic
+        let controller = discover_controller_id(&metadata)?;
+        client
+            .describe_configs(
+                &[ResourceSpecifier::Broker(controller)],
+                &AdminOptions::new().request_timeout(Some(Duration::from_secs(5))),
             )
-        })?;
+            .await
+            .with_context(|| {
+                format!(
+                    "error fetching configuration from controller {} when creating new topic {} for sink",
+                    controller, topic
+                )
+            })
+    })
+    .await?;
 
     if configs.len() != 1 {
         Err(anyhow!(
-                "error creating topic {} for sink: broker {} returned {} config results, but one was expected",
+                "error creating topic {} for sink: controller returned {} config results, but one was expected",
                 topic,
-                broker,
                 configs.len()
             ))?;
     }
@@ -141,11 +207,140 @@ async fn discover_topic_configs<C: ClientContext>(
     })
 }
 
+/// Controls how [`ensure_kafka_topic`] handles a topic that already exists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TopicConfigReconciliation {
+    /// Don't inspect the live topic's configuration at all. This is the
+    /// historical behavior.
+    Ignore,
+    /// Compare the live topic's configuration, partition count, and
+    /// replication factor against what was requested, and return an error
+    /// listing every mismatch.
+    Verify,
+    /// Like `Verify`, but instead of failing, push an alter-configs request
+    /// (the non-incremental `AlterConfigs` API) that sets the drifted
+    /// configuration entries back to their requested values. Entries absent
+    /// from the live topic, partition count, and replication factor are not
+    /// altered; drift in those is only logged, never corrected.
+    Fix,
+}
+
+/// Controls how [`ensure_kafka_topic`] assigns replicas when creating a new
+/// topic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplicaPlacement {
+    /// Let the broker choose replica placement, via `TopicReplication::Fixed`.
+    /// This is the historical behavior.
+    BrokerAssigned,
+    /// Compute an explicit, rack-aware replica assignment ourselves via
+    /// rendezvous hashing, and request it with `TopicReplication::Variable`.
+    /// See [`compute_rack_aware_assignment`] for the algorithm.
+    RackAware,
+}
+
+/// Computes the highest-random-weight score of `broker_id` for `partition`,
+/// via a well-mixed, process-stable 64-bit hash. `DefaultHasher` is seeded
+/// with fixed keys (unlike the randomly-seeded `RandomState` used by
+/// `HashMap`), so this score is reproducible across runs and processes.
+fn rendezvous_score(partition: i32, broker_id: i32) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    partition.hash(&mut hasher);
+    broker_id.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Computes an explicit, rack-aware replica assignment for a new topic with
+/// `partition_count` partitions and `replication_factor` replicas each, using
+/// rendezvous (highest-random-weight) hashing.
+///
+/// For each partition, every live broker is scored via
+/// [`rendezvous_score`] and ranked highest-first; the highest-scoring broker
+/// becomes that partition's preferred leader. Because a partition's ranking
+/// depends only on its own hash, adding or removing a broker only relocates
+/// the partitions whose top-scoring broker actually changed, unlike a
+/// round-robin assignment which reshuffles everything.
+///
+/// Within that ranking, a broker is skipped in favor of a later, lower-scored
+/// one if its rack is already represented in the partition's assignment so
+/// far; once every distinct rack among the live brokers has a replica, the
+/// rack constraint is dropped and the remaining slots are filled in score
+/// order regardless of rack.
+fn compute_rack_aware_assignment(
+    metadata: &Metadata,
+    partition_count: i32,
+    replication_factor: i32,
+) -> Result<Vec<Vec<i32>>, anyhow::Error> {
+    let brokers: Vec<(i32, Option<String>)> = metadata
+        .brokers()
+        .iter()
+        .map(|b| (b.id(), b.rack().map(str::to_string)))
+        .collect();
+    if brokers.is_empty() {
+        bail!("cannot compute a rack-aware replica assignment with zero live brokers");
+    }
+
+    let replication_factor = usize::try_from(replication_factor)
+        .context("replication factor must be non-negative to compute a replica assignment")?;
+    if replication_factor > brokers.len() {
+        bail!(
+            "replication factor {} exceeds the number of live brokers ({})",
+            replication_factor,
+            brokers.len()
+        );
+    }
+    let partition_count = usize::try_from(partition_count)
+        .context("partition count must be non-negative to compute a replica assignment")?;
+
+    let mut assignment = Vec::with_capacity(partition_count);
+    for p in 0..partition_count {
+        let partition = i32::try_from(p).expect("partition count bounded by i32 above");
+        let mut ranked = brokers.clone();
+        ranked.sort_by_key(|(id, _)| std::cmp::Reverse(rendezvous_score(partition, *id)));
+
+        let mut replicas = Vec::with_capacity(replication_factor);
+        let mut used_racks = std::collections::BTreeSet::new();
+        for (id, rack) in &ranked {
+            if replicas.len() == replication_factor {
+                break;
+            }
+            match rack {
+                Some(r) if used_racks.contains(r) => continue,
+                Some(r) => {
+                    used_racks.insert(r.clone());
+                    replicas.push(*id);
+                }
+                None => replicas.push(*id),
+            }
+        }
+        if replicas.len() < replication_factor {
+            for (id, _) in &ranked {
+                if replicas.len() == replication_factor {
+                    break;
+                }
+                if !replicas.contains(id) {
+                    replicas.push(*id);
+                }
+            }
+        }
+        assignment.push(replicas);
+    }
+
+    Ok(assignment)
+}
+
 /// Ensures that the named Kafka topic exists.
 ///
 /// If the topic does not exist, the function creates the topic with the
-/// provided `config`. Note that if the topic already exists, the function does
-/// *not* verify that the topic's configuration matches `config`.
+/// provided `config`, placing replicas according to `replica_placement`. If
+/// the topic already exists, `reconciliation` controls whether and how its
+/// live configuration is checked against `config`, and `grow_partitions`, if
+/// set, additionally grows the topic's partition count up to the requested
+/// `partition_count` (never down — Kafka can't shrink a topic's partition
+/// count, and an impossible shrink request is always an error). Growing
+/// partitions reshuffles which partition a given key maps to, so this is
+/// opt-in: leave it unset for keyed sinks that depend on a stable
+/// partition-to-key mapping.
 ///
 /// Returns a boolean indicating whether the topic already existed.
 pub async fn ensure_kafka_topic(
@@ -157,6 +352,9 @@ pub async fn ensure_kafka_topic(
         mut replication_factor,
         topic_config,
     }: &KafkaSinkTopicOptions,
+    reconciliation: TopicConfigReconciliation,
+    grow_partitions: bool,
+    replica_placement: ReplicaPlacement,
 ) -> Result<bool, anyhow::Error> {
     let client: AdminClient<_> = connection
         .connection
@@ -169,15 +367,15 @@ pub async fn ensure_kafka_topic(
         )
         .await
         .add_context("creating admin client failed")?;
+    let fetch_timeout = storage_configuration
+        .parameters
+        .kafka_timeout_config
+        .fetch_metadata_timeout;
     // If either partition count or replication factor should be defaulted to the broker's config
     // (signaled by a value of None), explicitly poll the broker to discover the defaults.
     // Newer versions of Kafka can instead send create topic requests with -1 and have this happen
     // behind the scenes, but this is unsupported and will result in errors on pre-2.4 Kafka.
     if partition_count.is_none() || replication_factor.is_none() {
-        let fetch_timeout = storage_configuration
-            .parameters
-            .kafka_timeout_config
-            .fetch_metadata_timeout;
         match discover_topic_configs(&client, topic, fetch_timeout).await {
             Ok(configs) => {
                 if partition_count.is_none() {
@@ -201,27 +399,287 @@ pub async fn ensure_kafka_topic(
         };
     }
 
-    let mut kafka_topic = NewTopic::new(
-        topic,
-        partition_count.expect("always set above"),
-        TopicReplication::Fixed(replication_factor.expect("always set above")),
-    );
+    let rack_aware_assignment = match replica_placement {
+        ReplicaPlacement::BrokerAssigned => None,
+        ReplicaPlacement::RackAware => {
+            let metadata = client.inner().fetch_metadata(None, fetch_timeout).with_context(|| {
+                format!(
+                    "error fetching metadata to compute replica assignment for topic {}",
+                    topic
+                )
+            })?;
+            Some(compute_rack_aware_assignment(
+                &metadata,
+                partition_count.expect("always set above"),
+                replication_factor.expect("always set above"),
+            )?)
+        }
+    };
+
+    let mut kafka_topic = match &rack_aware_assignment {
+        None => NewTopic::new(
+            topic,
+            partition_count.expect("always set above"),
+            TopicReplication::Fixed(replication_factor.expect("always set above")),
+        ),
+        Some(assignment) => NewTopic::new(
+            topic,
+            partition_count.expect("always set above"),
+            TopicReplication::Variable(assignment),
+        ),
+    };
 
     for (key, value) in topic_config {
         kafka_topic = kafka_topic.set(key, value);
     }
 
-    mz_kafka_util::admin::ensure_topic(
-        &client,
-        &AdminOptions::new().request_timeout(Some(Duration::from_secs(5))),
-        &kafka_topic,
-    )
-    .await
-    .with_context(|| format!("Error creating topic {} for sink", topic))
+    let already_exists = with_controller_retry(topic, "creating topic", || async {
+        mz_kafka_util::admin::ensure_topic(
+            &client,
+            &AdminOptions::new().request_timeout(Some(Duration::from_secs(5))),
+            &kafka_topic,
+        )
+        .await
+        .with_context(|| format!("Error creating topic {} for sink", topic))
+    })
+    .await?;
+
+    if already_exists && grow_partitions {
+        grow_topic_partitions(
+            &client,
+            topic,
+            partition_count.expect("always set above"),
+            fetch_timeout,
+        )
+        .await?;
+    }
+
+    if already_exists && reconciliation != TopicConfigReconciliation::Ignore {
+        reconcile_topic_config(
+            &client,
+            topic,
+            partition_count.expect("always set above"),
+            replication_factor.expect("always set above"),
+            topic_config,
+            reconciliation,
+            fetch_timeout,
+        )
+        .await?;
+    }
+
+    Ok(already_exists)
+}
+
+/// If `topic` already exists with fewer partitions than `partition_count`,
+/// issues a `NewPartitions` request to grow it to match. Kafka does not
+/// support shrinking a topic's partition count, so this returns an error if
+/// `partition_count` is lower than the topic's current partition count.
+async fn grow_topic_partitions<C: ClientContext>(
+    client: &AdminClient<C>,
+    topic: &str,
+    partition_count: i32,
+    fetch_timeout: Duration,
+) -> Result<(), anyhow::Error> {
+    if partition_count == -1 {
+        // The caller asked for the broker default; there's no explicit target to grow to.
+        return Ok(());
+    }
+
+    let metadata = client
+        .inner()
+        .fetch_metadata(Some(topic), fetch_timeout)
+        .with_context(|| format!("error fetching metadata for existing topic {}", topic))?;
+    let topic_metadata = metadata
+        .topics()
+        .iter()
+        .find(|t| t.name() == topic)
+        .ok_or_else(|| anyhow!("topic {} missing from metadata response", topic))?;
+    let current_partition_count = i32::try_from(topic_metadata.partitions().len())
+        .with_context(|| format!("partition count for topic {} overflowed i32", topic))?;
+
+    match partition_count.cmp(&current_partition_count) {
+        Ordering::Less => bail!(
+            "cannot shrink topic {} from {} partitions to {}; Kafka does not support decreasing partition counts",
+            topic,
+            current_partition_count,
+            partition_count
+        ),
+        Ordering::Equal => Ok(()),
+        Ordering::Greater => {
+            warn!(
+                "growing topic {} from {} to {} partitions",
+                topic, current_partition_count, partition_count
+            );
+            let new_partition_count = usize::try_from(partition_count)
+                .with_context(|| format!("partition count for topic {} overflowed usize", topic))?;
+            with_controller_retry(topic, "growing partitions", || async {
+                client
+                    .create_partitions(
+                        &[NewPartitions::new(topic, new_partition_count)],
+                        &AdminOptions::new().request_timeout(Some(Duration::from_secs(5))),
+                    )
+                    .await
+                    .with_context(|| format!("error growing partitions for topic {}", topic))?
+                    .into_element()
+                    .map_err(|e| {
+                        anyhow!(
+                            "error reading create_partitions response for topic {}: {}",
+                            topic,
+                            e
+                        )
+                    })?
+                    .map_err(|e| anyhow!("error growing partitions for topic {}: {}", topic, e))
+            })
+            .await?;
+            Ok(())
+        }
+    }
+}
+
+/// Compares the live configuration of an existing topic against what the
+/// sink expects, per `reconciliation`.
+async fn reconcile_topic_config<C: ClientContext>(
+    client: &AdminClient<C>,
+    topic: &str,
+    expected_partition_count: i32,
+    expected_replication_factor: i32,
+    topic_config: &BTreeMap<String, String>,
+    reconciliation: TopicConfigReconciliation,
+    fetch_timeout: Duration,
+) -> Result<(), anyhow::Error> {
+    let metadata = client
+        .inner()
+        .fetch_metadata(Some(topic), fetch_timeout)
+        .with_context(|| format!("error fetching metadata for existing topic {}", topic))?;
+    let topic_metadata = metadata
+        .topics()
+        .iter()
+        .find(|t| t.name() == topic)
+        .ok_or_else(|| anyhow!("topic {} missing from metadata response", topic))?;
+
+    let mut mismatches = vec![];
+
+    let actual_partition_count = i32::try_from(topic_metadata.partitions().len())
+        .with_context(|| format!("partition count for topic {} overflowed i32", topic))?;
+    if expected_partition_count != -1 && actual_partition_count != expected_partition_count {
+        mismatches.push(format!(
+            "partition count: expected {}, found {}",
+            expected_partition_count, actual_partition_count
+        ));
+    }
+
+    let actual_replication_factor = topic_metadata
+        .partitions()
+        .first()
+        .map_or(0, |p| p.replicas().len());
+    let actual_replication_factor = i32::try_from(actual_replication_factor)
+        .with_context(|| format!("replication factor for topic {} overflowed i32", topic))?;
+    if expected_replication_factor != -1 && actual_replication_factor != expected_replication_factor
+    {
+        mismatches.push(format!(
+            "replication factor: expected {}, found {}",
+            expected_replication_factor, actual_replication_factor
+        ));
+    }
+
+    let configs = client
+        .describe_configs(
+            &[ResourceSpecifier::Topic(topic)],
+            &AdminOptions::new().request_timeout(Some(Duration::from_secs(5))),
+        )
+        .await
+        .with_context(|| format!("error fetching configuration for existing topic {}", topic))?;
+    let config = configs
+        .into_element()
+        .map_err(|e| anyhow!("error reading topic configuration for {}: {}", topic, e))?;
+
+    let live: BTreeMap<_, _> = config
+        .entries
+        .into_iter()
+        .map(|e| (e.name, e.value))
+        .collect();
+
+    let mut to_alter = BTreeMap::new();
+    for (key, expected) in topic_config {
+        match live.get(key) {
+            Some(Some(actual)) if actual == expected => {}
+            Some(actual) => {
+                mismatches.push(format!(
+                    "{}: expected {}, found {}",
+                    key,
+                    expected,
+                    actual.as_deref().unwrap_or("<unset>")
+                ));
+                to_alter.insert(key.clone(), expected.clone());
+            }
+            None => {
+                mismatches.push(format!(
+                    "{}: expected {}, but key is not present on the live topic",
+                    key, expected
+                ));
+            }
+        }
+    }
+
+    if mismatches.is_empty() {
+        return Ok(());
+    }
+
+    match reconciliation {
+        TopicConfigReconciliation::Ignore => Ok(()),
+        TopicConfigReconciliation::Verify => Err(anyhow!(
+            "topic {} configuration has drifted from the requested configuration:\n{}",
+            topic,
+            mismatches.join("\n")
+        )),
+        TopicConfigReconciliation::Fix => {
+            warn!(
+                "topic {} configuration has drifted from the requested configuration, correcting: {}",
+                topic,
+                mismatches.join("; ")
+            );
+            if !to_alter.is_empty() {
+                with_controller_retry(topic, "altering configuration", || async {
+                    let mut alter_config = AlterConfig::new(ResourceSpecifier::Topic(topic));
+                    for (key, value) in &to_alter {
+                        alter_config = alter_config.set(key, value);
+                    }
+                    client
+                        .alter_configs(
+                            &[alter_config],
+                            &AdminOptions::new().request_timeout(Some(Duration::from_secs(5))),
+                        )
+                        .await
+                        .with_context(|| format!("error altering configuration for topic {}", topic))?
+                        .into_element()
+                        .map_err(|e| {
+                            anyhow!(
+                                "error reading alter_configs response for topic {}: {}",
+                                topic,
+                                e
+                            )
+                        })?
+                        .map_err(|e| {
+                            anyhow!("error altering configuration for topic {}: {}", topic, e)
+                        })
+                })
+                .await?;
+            }
+            Ok(())
+        }
+    }
 }
 
 /// Publish value and optional key schemas for a given topic.
 ///
+/// `value_schema_references` and `key_schema_references` are published
+/// alongside their respective schema so that schemas which `$ref`/import
+/// other subjects (e.g. a shared Protobuf envelope, or Avro records reused
+/// across topics) can be registered. Callers are expected to have already
+/// resolved and, if necessary, incrementally registered those dependency
+/// subjects themselves; this function only forwards the resulting reference
+/// list to the registry.
+///
 /// TODO(benesch): do we need to delete the Kafka topic if publishing the
 /// schema fails?
 pub async fn publish_kafka_schemas(
@@ -229,15 +687,17 @@ pub async fn publish_kafka_schemas(
     topic: String,
     key_schema: Option<String>,
     key_schema_type: Option<mz_ccsr::SchemaType>,
+    key_schema_references: &[mz_ccsr::SchemaReference],
     value_schema: &str,
     value_schema_type: mz_ccsr::SchemaType,
+    value_schema_references: &[mz_ccsr::SchemaReference],
 ) -> Result<(Option<i32>, i32), anyhow::Error> {
     let value_schema_id = ccsr
         .publish_schema(
             &format!("{}-value", topic),
             value_schema,
             value_schema_type,
-            &[],
+            value_schema_references,
         )
         .await
         .context("unable to publish value schema to registry in kafka sink")?;
@@ -245,10 +705,16 @@ pub async fn publish_kafka_schemas(
     let key_schema_id = if let Some(key_schema) = key_schema {
         let key_schema_type =
             key_schema_type.ok_or_else(|| anyhow!("expected schema type for key schema"))?;
+        let key_schema_references = key_schema_references.to_vec();
         Some(
             async move {
-                ccsr.publish_schema(&format!("{}-key", topic), &key_schema, key_schema_type, &[])
-                    .await
+                ccsr.publish_schema(
+                    &format!("{}-key", topic),
+                    &key_schema,
+                    key_schema_type,
+                    &key_schema_references,
+                )
+                .await
             }
             .run_in_task(|| "publish_kafka_schemas".to_string())
             .await